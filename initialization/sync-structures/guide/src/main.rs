@@ -0,0 +1,342 @@
+use ash::vk;
+use ash::vk::TaggedStructure;
+use std::ffi::{CStr, CString};
+
+#[cfg(debug_assertions)]
+const VALIDATION_ENABLED: bool = true;
+#[cfg(not(debug_assertions))]
+const VALIDATION_ENABLED: bool = false;
+
+/// Extensions and features a physical device must support to be considered usable.
+/// `Engine::new` rejects every device that falls short instead of discovering the
+/// gap later as a device-creation failure or a validation error.
+#[derive(Default, Clone)]
+pub struct DeviceRequirements {
+    pub extensions: Vec<CString>,
+    pub synchronization2: bool,
+}
+
+struct PhysicalDeviceInfo {
+    handle: vk::PhysicalDevice,
+    properties: vk::PhysicalDeviceProperties,
+    extensions: Vec<CString>,
+    synchronization2: bool,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+}
+
+impl PhysicalDeviceInfo {
+    fn local_memory_size(&self) -> vk::DeviceSize {
+        self.memory_properties.memory_heaps[..self.memory_properties.memory_heap_count as usize]
+            .iter()
+            .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+            .map(|heap| heap.size)
+            .sum()
+    }
+
+    fn is_suitable(&self, requirements: &DeviceRequirements) -> bool {
+        if requirements.synchronization2 && !self.synchronization2 {
+            return false;
+        }
+
+        requirements
+            .extensions
+            .iter()
+            .all(|extension| self.extensions.contains(extension))
+    }
+}
+
+unsafe fn gather_physical_device_info(
+    instance: &ash::Instance,
+    handle: vk::PhysicalDevice,
+) -> anyhow::Result<PhysicalDeviceInfo> {
+    let properties = instance.get_physical_device_properties(handle);
+    let memory_properties = instance.get_physical_device_memory_properties(handle);
+
+    let extensions = instance
+        .enumerate_device_extension_properties(handle)?
+        .iter()
+        .map(|extension| CStr::from_ptr(extension.extension_name.as_ptr()).to_owned())
+        .collect();
+
+    let mut vulkan13_features = vk::PhysicalDeviceVulkan13Features::default();
+    let mut features2 = vk::PhysicalDeviceFeatures2::default().push(&mut vulkan13_features);
+    instance.get_physical_device_features2(handle, &mut features2);
+
+    Ok(PhysicalDeviceInfo {
+        handle,
+        properties,
+        extensions,
+        synchronization2: vulkan13_features.synchronization2 == vk::TRUE,
+        memory_properties,
+    })
+}
+
+fn score_physical_device(info: &PhysicalDeviceInfo) -> (i32, vk::DeviceSize) {
+    let type_rank = match info.properties.device_type {
+        vk::PhysicalDeviceType::DISCRETE_GPU => 0,
+        vk::PhysicalDeviceType::INTEGRATED_GPU => 1,
+        _ => 2,
+    };
+
+    (-type_rank, info.local_memory_size())
+}
+
+pub struct Engine {
+    _entry: ash::Entry,
+    pub instance: ash::Instance,
+    pub physical_device: vk::PhysicalDevice,
+    pub device: ash::Device,
+
+    pub queue: vk::Queue,
+    pub queue_family: u32,
+
+    pub transfer_queue: vk::Queue,
+    pub transfer_queue_family: u32,
+
+    pub compute_queue: vk::Queue,
+    pub compute_queue_family: u32,
+
+    pub command_pool: vk::CommandPool,
+    pub cmd: vk::CommandBuffer,
+
+    pub render_fence: vk::Fence,
+
+    debug_utils: Option<ash::ext::debug_utils::Instance>,
+    debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
+}
+
+unsafe extern "system" fn vulkan_debug_callback(
+    message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    p_callback_data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+    _user_data: *mut std::ffi::c_void,
+) -> vk::Bool32 {
+    let message = CStr::from_ptr((*p_callback_data).p_message).to_string_lossy();
+
+    match message_severity {
+        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => log::error!("{message_type:?} {message}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => log::warn!("{message_type:?} {message}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::INFO => log::info!("{message_type:?} {message}"),
+        _ => log::debug!("{message_type:?} {message}"),
+    }
+
+    vk::FALSE
+}
+
+fn debug_messenger_create_info() -> vk::DebugUtilsMessengerCreateInfoEXT<'static> {
+    vk::DebugUtilsMessengerCreateInfoEXT::default()
+        .message_severity(
+            vk::DebugUtilsMessageSeverityFlagsEXT::ERROR
+                | vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+                | vk::DebugUtilsMessageSeverityFlagsEXT::INFO,
+        )
+        .message_type(
+            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+        )
+        .pfn_user_callback(Some(vulkan_debug_callback))
+}
+
+fn find_dedicated_queue_family(
+    queue_families: &[vk::QueueFamilyProperties],
+    wanted: vk::QueueFlags,
+    excluding: vk::QueueFlags,
+    fallback: u32,
+) -> u32 {
+    queue_families
+        .iter()
+        .position(|properties| {
+            properties.queue_flags.contains(wanted) && !properties.queue_flags.intersects(excluding)
+        })
+        .map(|index| index as u32)
+        .unwrap_or(fallback)
+}
+
+impl Engine {
+    pub fn new(requirements: &DeviceRequirements) -> anyhow::Result<Self> {
+        unsafe {
+            let entry = ash::Entry::load()?;
+
+            let layer_names = [CString::new("VK_LAYER_KHRONOS_validation")?];
+            let layer_name_ptrs: Vec<_> = layer_names.iter().map(|name| name.as_ptr()).collect();
+            let extension_names = [ash::ext::debug_utils::NAME.as_ptr()];
+
+            let mut debug_create_info = debug_messenger_create_info();
+            let mut instance_info = vk::InstanceCreateInfo::default().application_info(
+                &vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_4),
+            );
+            if VALIDATION_ENABLED {
+                instance_info = instance_info
+                    .enabled_layer_names(&layer_name_ptrs)
+                    .enabled_extension_names(&extension_names)
+                    .push(&mut debug_create_info);
+            }
+            let instance = entry.create_instance(&instance_info, None)?;
+
+            let (debug_utils, debug_messenger) = if VALIDATION_ENABLED {
+                let debug_utils = ash::ext::debug_utils::Instance::new(&entry, &instance);
+                let debug_messenger =
+                    debug_utils.create_debug_utils_messenger(&debug_create_info, None)?;
+                (Some(debug_utils), Some(debug_messenger))
+            } else {
+                (None, None)
+            };
+
+            let candidates: Vec<_> = instance
+                .enumerate_physical_devices()?
+                .into_iter()
+                .map(|handle| gather_physical_device_info(&instance, handle))
+                .collect::<anyhow::Result<_>>()?;
+
+            let physical_device_info = candidates
+                .into_iter()
+                .filter(|info| info.is_suitable(requirements))
+                .max_by_key(score_physical_device)
+                .ok_or(anyhow::anyhow!("No physical device satisfies the device requirements"))?;
+            let physical_device = physical_device_info.handle;
+
+            let queue_families = instance.get_physical_device_queue_family_properties(physical_device);
+            let queue_family = queue_families
+                .iter()
+                .position(|properties| {
+                    properties.queue_flags.contains(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE | vk::QueueFlags::TRANSFER)
+                })
+                .ok_or(anyhow::anyhow!("No main queue available"))? as u32;
+
+            let transfer_queue_family = find_dedicated_queue_family(
+                &queue_families,
+                vk::QueueFlags::TRANSFER,
+                vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE,
+                queue_family,
+            );
+            let compute_queue_family = find_dedicated_queue_family(
+                &queue_families,
+                vk::QueueFlags::COMPUTE,
+                vk::QueueFlags::GRAPHICS,
+                queue_family,
+            );
+
+            let mut unique_families = vec![queue_family];
+            for family in [transfer_queue_family, compute_queue_family] {
+                if !unique_families.contains(&family) {
+                    unique_families.push(family);
+                }
+            }
+            let queue_create_infos: Vec<_> = unique_families
+                .iter()
+                .map(|family| vk::DeviceQueueCreateInfo::default()
+                    .queue_family_index(*family)
+                    .queue_priorities(&[1.0])
+                )
+                .collect();
+
+            let extension_name_ptrs: Vec<_> = requirements
+                .extensions
+                .iter()
+                .map(|extension| extension.as_ptr())
+                .collect();
+
+            let device = instance.create_device(physical_device, &vk::DeviceCreateInfo::default()
+                .push(&mut vk::PhysicalDeviceVulkan13Features::default()
+                    .synchronization2(requirements.synchronization2)
+                )
+                .queue_create_infos(&queue_create_infos)
+                .enabled_extension_names(&extension_name_ptrs),
+            None)?;
+
+            let queue = device.get_device_queue(queue_family, 0);
+            let transfer_queue = device.get_device_queue(transfer_queue_family, 0);
+            let compute_queue = device.get_device_queue(compute_queue_family, 0);
+
+            let command_pool = device.create_command_pool(&vk::CommandPoolCreateInfo::default()
+                .queue_family_index(queue_family),
+            None)?;
+            let cmd = device.allocate_command_buffers(&vk::CommandBufferAllocateInfo::default()
+                .command_pool(command_pool)
+                .command_buffer_count(1)
+            )?[0];
+
+            let render_fence = device.create_fence(&vk::FenceCreateInfo::default(), None)?;
+
+            log::info!("Selected physical device: {:?}", CStr::from_ptr(physical_device_info.properties.device_name.as_ptr()));
+
+            Ok(Self {
+                _entry: entry,
+                instance, physical_device, device,
+                queue, queue_family,
+                transfer_queue, transfer_queue_family,
+                compute_queue, compute_queue_family,
+                command_pool, cmd,
+                render_fence,
+                debug_utils, debug_messenger,
+            })
+        }
+    }
+
+    pub fn run(&self) -> anyhow::Result<()> {
+        unsafe {
+            self.device.reset_fences(&[self.render_fence])?;
+
+            self.device.begin_command_buffer(self.cmd, &vk::CommandBufferBeginInfo::default()
+                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT)
+            )?;
+            // Commands here
+            self.device.end_command_buffer(self.cmd)?;
+
+            self.device.queue_submit2(self.queue, &[vk::SubmitInfo2::default()
+                .command_buffer_infos(&[vk::CommandBufferSubmitInfo::default()
+                    .command_buffer(self.cmd)
+                ])
+            ], self.render_fence)?;
+            Ok(())
+        }
+    }
+
+    /// Blocks until the most recent submission's fence is signaled, or `timeout` nanoseconds
+    /// elapse.
+    pub fn wait(&self, timeout: u64) -> anyhow::Result<()> {
+        unsafe {
+            self.device.wait_for_fences(&[self.render_fence], true, timeout)?;
+            Ok(())
+        }
+    }
+
+    /// Non-blocking check for whether the most recent submission has finished on the GPU.
+    pub fn poll(&self) -> anyhow::Result<bool> {
+        unsafe { Ok(self.device.get_fence_status(self.render_fence)?) }
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.device.device_wait_idle();
+
+            self.device.destroy_fence(self.render_fence, None);
+            self.device.destroy_command_pool(self.command_pool, None);
+            self.device.destroy_device(None);
+
+            if let (Some(debug_utils), Some(debug_messenger)) =
+                (self.debug_utils.take(), self.debug_messenger.take())
+            {
+                debug_utils.destroy_debug_utils_messenger(debug_messenger, None);
+            }
+
+            self.instance.destroy_instance(None);
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let requirements = DeviceRequirements {
+        extensions: vec![],
+        synchronization2: true,
+    };
+
+    let engine = Engine::new(&requirements)?;
+    engine.run()?;
+    engine.wait(u64::MAX)?;
+
+    Ok(())
+}
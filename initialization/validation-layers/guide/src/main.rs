@@ -0,0 +1,176 @@
+use ash::vk;
+use ash::vk::TaggedStructure;
+use std::ffi::{CStr, CString};
+
+#[cfg(debug_assertions)]
+const VALIDATION_ENABLED: bool = true;
+#[cfg(not(debug_assertions))]
+const VALIDATION_ENABLED: bool = false;
+
+pub struct Engine {
+    _entry: ash::Entry,
+    pub instance: ash::Instance,
+    pub physical_device: vk::PhysicalDevice,
+    pub device: ash::Device,
+
+    pub queue: vk::Queue,
+    pub queue_family: u32,
+
+    pub command_pool: vk::CommandPool,
+    pub cmd: vk::CommandBuffer,
+
+    debug_utils: Option<ash::ext::debug_utils::Instance>,
+    debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
+}
+
+unsafe extern "system" fn vulkan_debug_callback(
+    message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    p_callback_data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+    _user_data: *mut std::ffi::c_void,
+) -> vk::Bool32 {
+    let message = CStr::from_ptr((*p_callback_data).p_message).to_string_lossy();
+
+    match message_severity {
+        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => log::error!("{message_type:?} {message}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => log::warn!("{message_type:?} {message}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::INFO => log::info!("{message_type:?} {message}"),
+        _ => log::debug!("{message_type:?} {message}"),
+    }
+
+    vk::FALSE
+}
+
+fn debug_messenger_create_info() -> vk::DebugUtilsMessengerCreateInfoEXT<'static> {
+    vk::DebugUtilsMessengerCreateInfoEXT::default()
+        .message_severity(
+            vk::DebugUtilsMessageSeverityFlagsEXT::ERROR
+                | vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+                | vk::DebugUtilsMessageSeverityFlagsEXT::INFO,
+        )
+        .message_type(
+            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+        )
+        .pfn_user_callback(Some(vulkan_debug_callback))
+}
+
+impl Engine {
+    pub fn new() -> anyhow::Result<Self> {
+        unsafe {
+            let entry = ash::Entry::load()?;
+
+            let layer_names = [CString::new("VK_LAYER_KHRONOS_validation")?];
+            let layer_name_ptrs: Vec<_> = layer_names.iter().map(|name| name.as_ptr()).collect();
+            let extension_names = [ash::ext::debug_utils::NAME.as_ptr()];
+
+            let mut debug_create_info = debug_messenger_create_info();
+            let mut instance_info = vk::InstanceCreateInfo::default().application_info(
+                &vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_4),
+            );
+            if VALIDATION_ENABLED {
+                instance_info = instance_info
+                    .enabled_layer_names(&layer_name_ptrs)
+                    .enabled_extension_names(&extension_names)
+                    .push(&mut debug_create_info);
+            }
+            let instance = entry.create_instance(&instance_info, None)?;
+
+            let (debug_utils, debug_messenger) = if VALIDATION_ENABLED {
+                let debug_utils = ash::ext::debug_utils::Instance::new(&entry, &instance);
+                let debug_messenger =
+                    debug_utils.create_debug_utils_messenger(&debug_create_info, None)?;
+                (Some(debug_utils), Some(debug_messenger))
+            } else {
+                (None, None)
+            };
+
+            let physical_device = instance
+                .enumerate_physical_devices()?
+                .into_iter()
+                .min_by_key(|physical_device| {
+                    match instance.get_physical_device_properties(*physical_device).device_type {
+                        vk::PhysicalDeviceType::DISCRETE_GPU => 0,
+                        vk::PhysicalDeviceType::INTEGRATED_GPU => 1,
+                        _ => 3,
+                    }
+                })
+                .ok_or(anyhow::anyhow!("No physical devices available"))?;
+            let queue_family = instance
+                .get_physical_device_queue_family_properties(physical_device)
+                .into_iter()
+                .position(|properties| {
+                    properties.queue_flags.contains(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE | vk::QueueFlags::TRANSFER)
+                })
+                .ok_or(anyhow::anyhow!("No main queue available"))? as u32;
+
+            let device = instance.create_device(physical_device, &vk::DeviceCreateInfo::default()
+                .push(&mut vk::PhysicalDeviceVulkan13Features::default()
+                    .synchronization2(true)
+                )
+                .queue_create_infos(&[vk::DeviceQueueCreateInfo::default()
+                    .queue_family_index(queue_family)
+                    .queue_priorities(&[1.0])
+                ]),
+            None)?;
+            let queue = device.get_device_queue(queue_family, 0);
+
+            let command_pool = device.create_command_pool(&vk::CommandPoolCreateInfo::default()
+                .queue_family_index(queue_family),
+            None)?;
+            let cmd = device.allocate_command_buffers(&vk::CommandBufferAllocateInfo::default()
+                .command_pool(command_pool)
+                .command_buffer_count(1)
+            )?[0];
+
+            Ok(Self {
+                _entry: entry,
+                instance, physical_device, device,
+                queue, queue_family,
+                command_pool, cmd,
+                debug_utils, debug_messenger,
+            })
+        }
+    }
+
+    pub fn run(&self) -> anyhow::Result<()> {
+        unsafe {
+            self.device.begin_command_buffer(self.cmd, &vk::CommandBufferBeginInfo::default()
+                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT)
+            )?;
+            // Commands here
+            self.device.end_command_buffer(self.cmd)?;
+
+            self.device.queue_submit2(self.queue, &[vk::SubmitInfo2::default()
+                .command_buffer_infos(&[vk::CommandBufferSubmitInfo::default()
+                    .command_buffer(self.cmd)
+                ])
+            ], vk::Fence::null())?;
+            self.device.queue_wait_idle(self.queue)?;
+            Ok(())
+        }
+    }
+
+    pub fn destroy(self) -> anyhow::Result<()> {
+        unsafe {
+            self.device.destroy_command_pool(self.command_pool, None);
+            self.device.destroy_device(None);
+
+            if let (Some(debug_utils), Some(debug_messenger)) = (self.debug_utils, self.debug_messenger) {
+                debug_utils.destroy_debug_utils_messenger(debug_messenger, None);
+            }
+
+            self.instance.destroy_instance(None);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let engine = Engine::new()?;
+    engine.run()?;
+    engine.destroy()?;
+
+    Ok(())
+}